@@ -41,17 +41,17 @@
 //! assert_eq!(gap_vec.remove().unwrap(), "foo".to_string());
 //! ```
 
-#![feature(core_intrinsics, alloc, raw_vec_internals)]
+#![feature(alloc, raw_vec_internals)]
 extern crate alloc;
 
-use core::intrinsics::assume;
-use core::ops::{Deref, DerefMut};
 use core::slice;
 
 use alloc::raw_vec::RawVec;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
-use std::ops::Range;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
 use std::ptr;
 
 /// A contiguous growable array type with heap-allocated contens and gap. 
@@ -68,6 +68,66 @@ use std::ptr;
 /// ```
 ///
 
+/// Creates a `GapVec` containing the given elements, leaving the
+/// insertion position at the end.
+///
+/// `gap_vec!` allows `GapVec`s to be defined with the same syntax as
+/// array expressions, analogous to the standard library's `vec!`.
+///
+/// There are two forms of this macro:
+///
+/// - Create a `GapVec` containing a given list of elements:
+///
+/// ```
+/// #[macro_use] extern crate gap_vec;
+/// # fn main() {
+/// let gap_vec = gap_vec![1, 2, 3];
+/// assert_eq!(gap_vec.get(0), Some(&1));
+/// assert_eq!(gap_vec.get(1), Some(&2));
+/// assert_eq!(gap_vec.get(2), Some(&3));
+/// # }
+/// ```
+///
+/// - Create a `GapVec` from a given element and size:
+///
+/// ```
+/// #[macro_use] extern crate gap_vec;
+/// # fn main() {
+/// let gap_vec = gap_vec![1; 3];
+/// assert_eq!(gap_vec.len(), 3);
+/// assert_eq!(gap_vec.get(0), Some(&1));
+/// assert_eq!(gap_vec.get(1), Some(&1));
+/// assert_eq!(gap_vec.get(2), Some(&1));
+/// assert_eq!(gap_vec.get(3), None);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! gap_vec {
+    () => {
+        $crate::GapVec::new()
+    };
+    ($elem:expr; $n:expr) => {
+        {
+            // `GapVec::with_capacity` starts with an empty gap (`0..0`),
+            // so it cannot be used for pre-sizing here: `len()` would
+            // count the reserved-but-uninitialized tail as live
+            // elements. Build on `new()` instead.
+            let mut gap_vec = $crate::GapVec::new();
+            for _ in 0..$n {
+                gap_vec.insert($elem);
+            }
+            gap_vec
+        }
+    };
+    ($($x:expr),+ $(,)*) => {
+        {
+            let mut gap_vec = $crate::GapVec::new();
+            $(gap_vec.insert($x);)+
+            gap_vec
+        }
+    };
+}
+
 pub struct GapVec<T> {
     buf: RawVec<T>,
     gap: Range<usize>,
@@ -325,7 +385,7 @@ impl<T> GapVec<T> {
     //
     // Safety: `index` must be less than self.capacity().
     unsafe fn space(&self, index: usize) -> *const T {
-        self.as_ptr().offset(index as isize)
+        self.buf.ptr().offset(index as isize)
     }
 
     // Returns a mutable pointer to the `index`'th element of the underlying buf,
@@ -333,7 +393,192 @@ impl<T> GapVec<T> {
     //
     // Safety: `index` must be less than self.capacity().
     unsafe fn space_mut(&mut self, index: usize) -> *mut T {
-        self.as_mut_ptr().offset(index as isize)
+        self.buf.ptr().offset(index as isize)
+    }
+
+    /// Returns the gap vector's contents as two slices, in order: the
+    /// elements before the gap, then the elements after the gap.
+    ///
+    /// Unlike a single contiguous slice, this never exposes the
+    /// uninitialized memory that the gap occupies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec = GapVec::new();
+    /// gap_vec.insert_iter(1..=4);
+    /// gap_vec.set_position(2);
+    /// gap_vec.insert(9);
+    ///
+    /// let (front, back) = gap_vec.as_slices();
+    /// assert_eq!(front, &[1, 2, 9]);
+    /// assert_eq!(back, &[3, 4]);
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        unsafe {
+            let front = slice::from_raw_parts(self.space(0), self.gap.start);
+            let back = slice::from_raw_parts(self.space(self.gap.end),
+                                              self.capacity() - self.gap.end);
+            (front, back)
+        }
+    }
+
+    /// Returns the gap vector's contents as two mutable slices, in order:
+    /// the elements before the gap, then the elements after the gap.
+    ///
+    /// See [`as_slices`](#method.as_slices) for details.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        unsafe {
+            let gap = self.gap.clone();
+            let cap = self.capacity();
+            let ptr = self.buf.ptr();
+            let front = slice::from_raw_parts_mut(ptr, gap.start);
+            let back = slice::from_raw_parts_mut(ptr.offset(gap.end as isize), cap - gap.end);
+            (front, back)
+        }
+    }
+
+    /// Returns a mutable iterator over the gap vector, in logical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec: GapVec<i32> = GapVec::new();
+    /// gap_vec.insert_iter(1..4);
+    /// for element in gap_vec.iter_mut() {
+    ///     *element += 1;
+    /// }
+    /// assert_eq!(gap_vec.get(0), Some(&2));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<T> {
+        let back = self.len();
+        IterMut { buf: self as *mut GapVec<T>, pos: 0, back, _marker: PhantomData }
+    }
+
+    /// Removes the logical range from the gap vector and returns an
+    /// iterator over the removed elements.
+    ///
+    /// If the iterator is dropped before being fully consumed, the
+    /// remaining elements are still removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point, or if
+    /// the end point is greater than `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec: GapVec<i32> = GapVec::new();
+    /// gap_vec.insert_iter(1..6);
+    /// let removed: Vec<_> = gap_vec.drain(1..3).collect();
+    /// assert_eq!(removed, vec![2, 3]);
+    /// assert_eq!(gap_vec.get(0), Some(&1));
+    /// ```
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<T> {
+        let range = self.resolve_range(range);
+        self.set_position(range.start);
+        Drain { gap_vec: self, consumed: 0, remaining: range.end - range.start }
+    }
+
+    // Turns a `RangeBounds<usize>` into a concrete `start..end`; an
+    // unbounded end becomes the gap vector's logical length.
+    //
+    // Panics if `start > end` or `end > len`.
+    fn resolve_range<R: RangeBounds<usize>>(&self, range: R) -> Range<usize> {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+
+        assert!(start <= end, "gap vector range start is greater than end");
+        assert!(end <= len, "gap vector range end is out of bounds");
+        start..end
+    }
+
+    /// Retains only the elements for which `f` returns `true`, dropping
+    /// the rest and compacting the kept elements into the gap's place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec: GapVec<i32> = GapVec::new();
+    /// gap_vec.insert_iter(1..6);
+    /// gap_vec.retain(|&x| x % 2 == 0);
+    /// assert_eq!(gap_vec.get(0), Some(&2));
+    /// assert_eq!(gap_vec.get(1), Some(&4));
+    /// assert_eq!(gap_vec.get(2), None);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&T) -> bool
+    {
+        self.retain_mut(|element| f(element));
+    }
+
+    /// Retains only the elements for which `f` returns `true`, dropping
+    /// the rest and compacting the kept elements into the gap's place.
+    ///
+    /// Unlike [`retain`](#method.retain), `f` is given a mutable
+    /// reference to each element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec: GapVec<i32> = GapVec::new();
+    /// gap_vec.insert_iter(1..6);
+    /// gap_vec.retain_mut(|x| {
+    ///     *x *= 10;
+    ///     *x <= 30
+    /// });
+    /// assert_eq!(gap_vec.get(0), Some(&10));
+    /// assert_eq!(gap_vec.get(1), Some(&20));
+    /// assert_eq!(gap_vec.get(2), Some(&30));
+    /// assert_eq!(gap_vec.get(3), None);
+    /// ```
+    pub fn retain_mut<F>(&mut self, mut f: F)
+        where F: FnMut(&mut T) -> bool
+    {
+        let len = self.len();
+        self.set_position(len);
+
+        let mut write = 0;
+        for read in 0..len {
+            unsafe {
+                let keep = f(&mut *self.space_mut(read));
+                if keep {
+                    if write != read {
+                        ptr::copy_nonoverlapping(self.space(read), self.space_mut(write), 1);
+                    }
+                    write += 1;
+                } else {
+                    ptr::drop_in_place(self.space_mut(read));
+                }
+            }
+
+            // Keep the gap boundary in lock-step with what's been
+            // processed so far: if `f` panics on a later element, only
+            // the already-compacted prefix is considered initialized,
+            // and everything from here on is treated as gap (and thus
+            // never double-dropped).
+            self.gap.start = write;
+        }
     }
 }
 
@@ -343,11 +588,119 @@ impl GapVec<char> {
         text.extend(self);
         text
     }
+
+    /// Returns the characters in the logical range `range` as a `String`,
+    /// without materializing the rest of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point, or if
+    /// the end point is greater than `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec: GapVec<char> = GapVec::new();
+    /// gap_vec.insert_str("Hello, world!");
+    /// assert_eq!(gap_vec.to_string_range(0..5), "Hello");
+    /// ```
+    pub fn to_string_range<R: RangeBounds<usize>>(&self, range: R) -> String {
+        let range = self.resolve_range(range);
+        range.map(|i| self[i]).collect()
+    }
+
+    /// Inserts the characters of `s` at the current insertion position,
+    /// and leaves the insertion position after them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec: GapVec<char> = GapVec::new();
+    /// gap_vec.insert_str("Foo bar");
+    /// assert_eq!(gap_vec.get_string(), "Foo bar");
+    /// ```
+    pub fn insert_str(&mut self, s: &str) {
+        self.insert_iter(s.chars());
+    }
+
+    /// Removes the logical range of characters `range`, moving the gap to
+    /// cover them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point, or if
+    /// the end point is greater than `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec: GapVec<char> = GapVec::new();
+    /// gap_vec.insert_str("Hello, world!");
+    /// gap_vec.remove_range(5..12);
+    /// assert_eq!(gap_vec.get_string(), "Hello!");
+    /// ```
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.drain(range).for_each(drop);
+    }
+
+    /// Replaces the logical range `range` with the characters of
+    /// `replacement`, leaving the insertion position right after the
+    /// replacement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point, or if
+    /// the end point is greater than `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gap_vec::GapVec;
+    ///
+    /// let mut gap_vec: GapVec<char> = GapVec::new();
+    /// gap_vec.insert_str("Hello, world!");
+    /// gap_vec.splice(7..12, "Rust");
+    /// assert_eq!(gap_vec.get_string(), "Hello, Rust!");
+    /// ```
+    pub fn splice<R: RangeBounds<usize>>(&mut self, range: R, replacement: &str) {
+        self.remove_range(range);
+        self.insert_str(replacement);
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 // Common trait implementations for Vec
 ////////////////////////////////////////////////////////////////////////////////
+impl<T> Extend<T> for GapVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.insert_iter(iter);
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for GapVec<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.insert_iter(iter.into_iter().cloned());
+    }
+}
+
+impl<T> FromIterator<T> for GapVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> GapVec<T> {
+        // `GapVec::with_capacity` starts with an empty gap (`0..0`), so
+        // it cannot be used for pre-sizing here: `len()` would count the
+        // reserved-but-uninitialized tail as live elements. Build on
+        // `new()` instead and let `insert` grow the gap as needed.
+        let mut gap_vec = GapVec::new();
+        gap_vec.insert_iter(iter);
+        gap_vec
+    }
+}
+
 impl<T: fmt::Debug> Debug for GapVec<T> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let indeces = (0..self.gap.start).chain(self.gap.end..self.capacity());
@@ -356,25 +709,36 @@ impl<T: fmt::Debug> Debug for GapVec<T> {
     }
 }
 
-impl<T> Deref for GapVec<T> {
-    type Target = [T];
+impl<T> Index<usize> for GapVec<T> {
+    type Output = T;
 
-    fn deref(&self) -> &[T] {
-        unsafe {
-            let ptr = self.buf.ptr();
-            assume(!ptr.is_null());
-            slice::from_raw_parts(ptr, self.len())
-        }
+    /// Returns a reference to the element at logical position `index`,
+    /// taking the gap into account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    fn index(&self, index: usize) -> &T {
+        self.get(index).unwrap_or_else(|| {
+            panic!("index {} out of range for GapVec buffer", index)
+        })
     }
 }
 
-impl<T> DerefMut for GapVec<T> {
-    fn deref_mut(&mut self) -> &mut [T] {
-        unsafe {
-            let ptr = self.buf.ptr();
-            assume(!ptr.is_null());
-            slice::from_raw_parts_mut(ptr, self.len())
+impl<T> IndexMut<usize> for GapVec<T> {
+    /// Returns a mutable reference to the element at logical position
+    /// `index`, taking the gap into account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let raw = self.index_to_raw(index);
+        if raw >= self.capacity() {
+            panic!("index {} out of range for GapVec buffer", index);
         }
+
+        unsafe { &mut *self.space_mut(raw) }
     }
 }
 
@@ -399,13 +763,14 @@ impl<T> Drop for GapVec<T> {
 
 pub struct Iter<'a, T: 'a> {
     buf: &'a GapVec<T>,
-    pos: usize
+    pos: usize,
+    back: usize,
 }
 
 impl<'a, T: 'a> Iterator for Iter<'a, T> {
     type Item = &'a T;
     fn next(&mut self) -> Option<&'a T> {
-        if self.pos >= self.buf.len() {
+        if self.pos >= self.back {
             None
         } else {
             self.pos += 1;
@@ -414,11 +779,125 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
     }
 }
 
+impl<'a, T: 'a> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.pos >= self.back {
+            None
+        } else {
+            self.back -= 1;
+            self.buf.get(self.back)
+        }
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.back - self.pos
+    }
+}
+
 impl<'a, T: 'a> IntoIterator for &'a GapVec<T> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
     fn into_iter(self) -> Iter<'a, T> {
-        Iter { buf: self, pos: 0 }
+        let back = self.len();
+        Iter { buf: self, pos: 0, back }
+    }
+}
+
+/// A mutable iterator for `GapVec<T>`.
+pub struct IterMut<'a, T: 'a> {
+    buf: *mut GapVec<T>,
+    pos: usize,
+    back: usize,
+    _marker: PhantomData<&'a mut GapVec<T>>,
+}
+
+impl<'a, T: 'a> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.pos >= self.back {
+            None
+        } else {
+            let index = self.pos;
+            self.pos += 1;
+            unsafe {
+                let gap_vec = &mut *self.buf;
+                let raw = gap_vec.index_to_raw(index);
+                Some(&mut *gap_vec.space_mut(raw))
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.pos >= self.back {
+            None
+        } else {
+            self.back -= 1;
+            unsafe {
+                let gap_vec = &mut *self.buf;
+                let raw = gap_vec.index_to_raw(self.back);
+                Some(&mut *gap_vec.space_mut(raw))
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.back - self.pos
+    }
+}
+
+impl<'a, T: 'a> IntoIterator for &'a mut GapVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// A draining iterator for `GapVec<T>`, created by [`GapVec::drain`].
+///
+/// [`GapVec::drain`]: struct.GapVec.html#method.drain
+pub struct Drain<'a, T: 'a> {
+    gap_vec: &'a mut GapVec<T>,
+    consumed: usize,
+    remaining: usize,
+}
+
+impl<'a, T: 'a> Iterator for Drain<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            None
+        } else {
+            let raw = self.gap_vec.gap.end + self.consumed;
+            self.consumed += 1;
+            self.remaining -= 1;
+            unsafe { Some(ptr::read(self.gap_vec.space(raw))) }
+        }
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for Drain<'a, T> {
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, T: 'a> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            for i in 0..self.remaining {
+                let raw = self.gap_vec.gap.end + self.consumed + i;
+                ptr::drop_in_place(self.gap_vec.space_mut(raw));
+            }
+        }
+
+        self.gap_vec.gap.end += self.consumed + self.remaining;
     }
 }
 